@@ -1,8 +1,9 @@
-use std::ops::{Neg, Add, AddAssign, Sub, SubAssign, Mul, MulAssign};
+use std::ops::{Neg, Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign};
 use std::cmp::Ordering;
 use rand::Rng;
 use rand::distributions::{Standard, Distribution};
 use serde::{Serialize, Deserialize};
+use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
 
 use super::Randomize;
 
@@ -109,12 +110,116 @@ impl Fp {
     pub fn prime() -> u128 {
         P
     }
+
+    #[inline]
+    pub fn is_zero(self) -> bool {
+        self.0 == 0 || self.0 == P
+    }
+
+    // Same as `is_zero`, but as a `Choice` instead of a branch, for callers
+    // that fold the result into further constant-time arithmetic rather
+    // than into control flow.
+    #[inline]
+    pub fn ct_is_zero(self) -> Choice {
+        let (h, l) = as_limbs(self.0);
+        let (ph, pl) = as_limbs(P);
+        (h.ct_eq(&0) & l.ct_eq(&0)) | (h.ct_eq(&ph) & l.ct_eq(&pl))
+    }
+
+    // Reduces the dual representation of zero (0 and P) down to 0 without
+    // branching on the secret value: `ConditionallySelectable` picks
+    // between 0 and self.0 based on a `Choice`, so no equality decision
+    // about the secret is observable via timing.
+    #[inline]
+    fn ct_canonical(self) -> u128 {
+        let (sh, sl) = as_limbs(self.0);
+        let (ph, pl) = as_limbs(P);
+        let is_p: Choice = sh.ct_eq(&ph) & sl.ct_eq(&pl);
+
+        u128::conditional_select(&self.0, &0, is_p)
+    }
+
+    // Compares two field elements in constant time, without leaking either
+    // operand through a data-dependent branch.
+    #[inline]
+    pub fn ct_eq(&self, other: &Self) -> Choice {
+        let (ah, al) = as_limbs(self.ct_canonical());
+        let (bh, bl) = as_limbs(other.ct_canonical());
+        ah.ct_eq(&bh) & al.ct_eq(&bl)
+    }
+
+    // Computes self^exp by square-and-multiply.
+    pub fn pow(self, mut exp: u128) -> Fp {
+        let mut base = self;
+        let mut result = Fp::from_u127(1);
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        result
+    }
+
+    // Computes the multiplicative inverse via Fermat's little theorem:
+    // a^(p-2) == a^-1 (mod p). Returns None for the zero element, which
+    // has two representations (0 and P) in this field. The exponentiation
+    // always runs, zero or not, so building the `Option` is the only
+    // place this branches on the secret.
+    pub fn inverse(self) -> Option<Fp> {
+        let is_zero = self.ct_is_zero();
+        let result = self.pow(P - 2);
+        if bool::from(is_zero) {
+            None
+        } else {
+            Some(result)
+        }
+    }
+
+    // Inverts every element of `elems` in place using Montgomery's trick:
+    // one forward pass of running products, a single `inverse()` of the
+    // total, then one backward pass recovering each individual inverse
+    // with two multiplications. This costs one inversion plus `3n`
+    // multiplications in total (zero elements included), instead of one
+    // inversion per element.
+    //
+    // Zero elements (which have two representations here) are skipped in
+    // the product chain and left untouched; their indices are returned.
+    // The skip is folded into the running product and the backward pass via
+    // `conditional_select` rather than a branch on `is_zero`; collecting the
+    // returned indices does branch on `is_zero`.
+    pub fn batch_inverse(elems: &mut [Fp]) -> Vec<usize> {
+        let n = elems.len();
+        let mut zero_indices = Vec::new();
+        let mut prefix = Vec::with_capacity(n);
+
+        let mut acc = Fp::from_u127(1);
+        for (i, &e) in elems.iter().enumerate() {
+            prefix.push(acc);
+            let is_zero = e.ct_is_zero();
+            if bool::from(is_zero) {
+                zero_indices.push(i);
+            }
+            acc = Fp::conditional_select(&(acc * e), &acc, is_zero);
+        }
+
+        let mut acc_inv = acc.inverse().expect("product of the non-zero elements is non-zero");
+        for i in (0..n).rev() {
+            let is_zero = elems[i].ct_is_zero();
+            let orig = elems[i];
+            elems[i] = Fp::conditional_select(&(prefix[i] * acc_inv), &orig, is_zero);
+            acc_inv = Fp::conditional_select(&(acc_inv * orig), &acc_inv, is_zero);
+        }
+
+        zero_indices
+    }
 }
 
 impl From<Fp> for u128 {
     #[inline]
     fn from(x: Fp) -> u128 {
-        if x.0 == P { 0 } else { x.0 }
+        x.ct_canonical()
     }
 }
 
@@ -200,10 +305,42 @@ impl MulAssign for Fp {
     }
 }
 
+impl Div for Fp {
+    type Output = Self;
+    // Division is multiplication by the Fermat inverse, not the plain
+    // arithmetic `/` clippy expects a `Div` impl to use.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    #[inline]
+    fn div(self, other: Self) -> Self {
+        self * other.inverse().expect("division by zero in Fp")
+    }
+}
+
+impl DivAssign for Fp {
+    #[inline]
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other
+    }
+}
+
+impl ConditionallySelectable for Fp {
+    #[inline]
+    fn conditional_select(a: &Fp, b: &Fp, choice: Choice) -> Fp {
+        Fp(u128::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl ConstantTimeEq for Fp {
+    #[inline]
+    fn ct_eq(&self, other: &Self) -> Choice {
+        Fp::ct_eq(self, other)
+    }
+}
+
 impl PartialEq for Fp {
     #[inline]
     fn eq(&self, other: &Self) -> bool {
-        u128::from(*self) == u128::from(*other)
+        self.ct_eq(other).into()
     }
 }
 
@@ -280,6 +417,75 @@ mod tests {
         assert!(Fp(23) > Fp(P));
     }
 
+    #[test]
+    fn pow() {
+        assert_eq!(Fp(3).pow(0), Fp(1));
+        assert_eq!(Fp(3).pow(1), Fp(3));
+        assert_eq!(Fp(3).pow(2), Fp(9));
+        assert_eq!(Fp(0).pow(0), Fp(1));
+    }
+
+    #[test]
+    fn inverse() {
+        assert_eq!(Fp(0).inverse(), None);
+        assert_eq!(Fp(P).inverse(), None);
+        assert_eq!(Fp(1).inverse(), Some(Fp(1)));
+
+        let a = Fp(123456789);
+        assert_eq!(a * a.inverse().unwrap(), Fp(1));
+    }
+
+    #[test]
+    fn div() {
+        assert_eq!(Fp(12) / Fp(3), Fp(4));
+        assert_eq!((Fp(12) / Fp(3)) * Fp(3), Fp(12));
+    }
+
+    #[test]
+    #[should_panic]
+    fn div_by_zero() {
+        let _ = Fp(5) / Fp(0);
+    }
+
+    #[test]
+    fn batch_inverse() {
+        let mut elems = vec![Fp(3), Fp(5), Fp(7), Fp(9)];
+        let originals = elems.clone();
+        let zero_indices = Fp::batch_inverse(&mut elems);
+
+        assert!(zero_indices.is_empty());
+        for (orig, inv) in originals.iter().zip(elems.iter()) {
+            assert_eq!(*orig * *inv, Fp(1));
+        }
+    }
+
+    #[test]
+    fn batch_inverse_skips_zero() {
+        let mut elems = vec![Fp(3), Fp(0), Fp(7), Fp(P)];
+        let zero_indices = Fp::batch_inverse(&mut elems);
+
+        assert_eq!(zero_indices, vec![1, 3]);
+        assert_eq!(elems[1], Fp(0));
+        assert_eq!(elems[3], Fp(P));
+        assert_eq!(Fp(3) * elems[0], Fp(1));
+        assert_eq!(Fp(7) * elems[2], Fp(1));
+    }
+
+    #[test]
+    fn ct_eq() {
+        use subtle::Choice;
+
+        assert_eq!(Fp(0).ct_eq(&Fp(P)).unwrap_u8(), Choice::from(1).unwrap_u8());
+        assert_eq!(Fp(17).ct_eq(&Fp(4)).unwrap_u8(), Choice::from(0).unwrap_u8());
+    }
+
+    #[test]
+    fn ct_is_zero() {
+        assert!(bool::from(Fp(0).ct_is_zero()));
+        assert!(bool::from(Fp(P).ct_is_zero()));
+        assert!(!bool::from(Fp(5).ct_is_zero()));
+    }
+
     #[test]
     fn assign() {
         let mut a = Fp(17);
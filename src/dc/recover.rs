@@ -0,0 +1,273 @@
+use rand::Rng;
+
+use super::fp::Fp;
+
+// A univariate polynomial over `Fp`, coefficients in ascending order of
+// degree (index i holds the coefficient of x^i). Trimmed so the last
+// entry, if any, is non-zero.
+type Poly = Vec<Fp>;
+
+// Error returned when the power sums do not decode to `n` distinct
+// messages.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RecoverError {
+    // Two or more participants submitted the same message, so the
+    // recovered polynomial is not squarefree and its roots do not
+    // account for all `n` slots. The protocol must identify and
+    // exclude the colliding slots and retry.
+    Collision,
+}
+
+fn zero() -> Fp {
+    Fp::from_u127(0)
+}
+
+fn one() -> Fp {
+    Fp::from_u127(1)
+}
+
+fn trim(mut p: Poly) -> Poly {
+    while let Some(&last) = p.last() {
+        if last.is_zero() {
+            p.pop();
+        } else {
+            break;
+        }
+    }
+    p
+}
+
+fn degree(p: &Poly) -> Option<usize> {
+    if p.is_empty() {
+        None
+    } else {
+        Some(p.len() - 1)
+    }
+}
+
+fn poly_sub(a: &Poly, b: &Poly) -> Poly {
+    let len = a.len().max(b.len());
+    let mut out = Vec::with_capacity(len);
+    for i in 0..len {
+        let av = a.get(i).copied().unwrap_or_else(zero);
+        let bv = b.get(i).copied().unwrap_or_else(zero);
+        out.push(av - bv);
+    }
+    trim(out)
+}
+
+fn poly_mul(a: &Poly, b: &Poly) -> Poly {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    let mut out = vec![zero(); a.len() + b.len() - 1];
+    for (i, &ai) in a.iter().enumerate() {
+        if ai.is_zero() {
+            continue;
+        }
+        for (j, &bj) in b.iter().enumerate() {
+            out[i + j] += ai * bj;
+        }
+    }
+    trim(out)
+}
+
+// Polynomial long division: returns (quotient, remainder) such that
+// a == quotient * b + remainder and deg(remainder) < deg(b).
+fn poly_divmod(a: &Poly, b: &Poly) -> (Poly, Poly) {
+    let b = trim(b.clone());
+    let b_deg = degree(&b).expect("poly_divmod: division by the zero polynomial");
+    let lead_inv = b[b_deg].inverse().expect("poly_divmod: leading coefficient is zero");
+
+    let mut rem = trim(a.clone());
+    let mut quot = Vec::new();
+    while let Some(rem_deg) = degree(&rem) {
+        if rem_deg < b_deg {
+            break;
+        }
+        let coeff = rem[rem_deg] * lead_inv;
+        let shift = rem_deg - b_deg;
+        if quot.len() <= shift {
+            quot.resize(shift + 1, zero());
+        }
+        quot[shift] = coeff;
+        for (i, &bi) in b.iter().enumerate() {
+            rem[i + shift] -= coeff * bi;
+        }
+        rem = trim(rem);
+    }
+    (trim(quot), rem)
+}
+
+fn poly_rem(a: &Poly, b: &Poly) -> Poly {
+    poly_divmod(a, b).1
+}
+
+fn poly_mulmod(a: &Poly, b: &Poly, modulus: &Poly) -> Poly {
+    poly_rem(&poly_mul(a, b), modulus)
+}
+
+// Computes base^exp mod modulus by square-and-multiply, reusing
+// `poly_mulmod` at every step.
+fn poly_powmod(base: &Poly, mut exp: u128, modulus: &Poly) -> Poly {
+    let mut result = vec![one()];
+    let mut b = poly_rem(base, modulus);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = poly_mulmod(&result, &b, modulus);
+        }
+        b = poly_mulmod(&b, &b, modulus);
+        exp >>= 1;
+    }
+    result
+}
+
+// Euclidean algorithm, normalized to a monic result.
+fn poly_gcd(a: &Poly, b: &Poly) -> Poly {
+    let mut x = trim(a.clone());
+    let mut y = trim(b.clone());
+    while !y.is_empty() {
+        let r = poly_rem(&x, &y);
+        x = y;
+        y = r;
+    }
+    if let Some(d) = degree(&x) {
+        let lead_inv = x[d].inverse().expect("poly_gcd: leading coefficient is zero");
+        for c in x.iter_mut() {
+            *c *= lead_inv;
+        }
+    }
+    x
+}
+
+// Recovers e_1..e_n, the elementary symmetric polynomials, from the power
+// sums S_1..S_n via Newton's identities. The result is [e_0, e_1, ..., e_n],
+// with e_0 = 1 at index 0.
+fn elementary_from_power_sums(power_sums: &[Fp]) -> Vec<Fp> {
+    let n = power_sums.len();
+    let mut e = Vec::with_capacity(n + 1);
+    e.push(one());
+    for k in 1..=n {
+        let mut acc = zero();
+        let mut positive = true;
+        for i in 1..=k {
+            let term = e[k - i] * power_sums[i - 1];
+            if positive {
+                acc += term;
+            } else {
+                acc -= term;
+            }
+            positive = !positive;
+        }
+        let k_inv = Fp::from_u127(k as u128).inverse().expect("k < p is never zero");
+        e.push(acc * k_inv);
+    }
+    e
+}
+
+// Builds the monic polynomial f(x) = x^n - e_1 x^(n-1) + e_2 x^(n-2) - ... (-1)^n e_n
+// whose roots are the recovered messages.
+fn poly_from_elementary(e: &[Fp]) -> Poly {
+    let n = e.len() - 1;
+    let mut coeffs = vec![zero(); n + 1];
+    for (j, &ej) in e.iter().enumerate() {
+        coeffs[n - j] = if j % 2 == 0 { ej } else { -ej };
+    }
+    coeffs
+}
+
+// Cantor-Zassenhaus equal-degree factorization, specialized to degree 1:
+// repeatedly splits `f` using a random affine polynomial until every
+// factor is linear, pushing each root into `roots`.
+fn find_roots<R: Rng + ?Sized>(f: &Poly, rng: &mut R, roots: &mut Vec<Fp>) {
+    match degree(f) {
+        None | Some(0) => {}
+        Some(1) => roots.push(-f[0]),
+        Some(d) => loop {
+            let r: Fp = rng.gen();
+            let affine = vec![r, one()];
+            let t = poly_powmod(&affine, (Fp::prime() - 1) / 2, f);
+            let t_minus_1 = poly_sub(&t, &vec![one()]);
+            let g = poly_gcd(f, &t_minus_1);
+            let g_deg = degree(&g).unwrap_or(0);
+            if g_deg > 0 && g_deg < d {
+                let (h, _) = poly_divmod(f, &g);
+                find_roots(&g, rng, roots);
+                find_roots(&h, rng, roots);
+                return;
+            }
+        },
+    }
+}
+
+// Recovers the `n` messages hidden behind the power sums `S_1..S_n` of a
+// DiceMix DC-net round. Stage one reconstructs the monic polynomial whose
+// roots are the messages via Newton's identities; stage two extracts the
+// roots via Cantor-Zassenhaus equal-degree factorization over `Fp`.
+//
+// Returns `RecoverError::Collision` if the polynomial is not squarefree,
+// i.e. two or more participants chose the same message.
+pub fn recover_messages<R: Rng + ?Sized>(
+    power_sums: &[Fp],
+    rng: &mut R,
+) -> Result<Vec<Fp>, RecoverError> {
+    let n = power_sums.len();
+    if n == 0 {
+        return Ok(Vec::new());
+    }
+
+    let e = elementary_from_power_sums(power_sums);
+    let f = poly_from_elementary(&e);
+
+    // gcd(f, x^p - x) isolates the distinct roots that lie in Fp; if f has
+    // a repeated root (a collision) this gcd has strictly smaller degree.
+    let x_poly = vec![zero(), one()];
+    let xp = poly_powmod(&x_poly, Fp::prime(), &f);
+    let xp_minus_x = poly_sub(&xp, &x_poly);
+    let squarefree_linear_part = poly_gcd(&f, &xp_minus_x);
+    if degree(&squarefree_linear_part) != Some(n) {
+        return Err(RecoverError::Collision);
+    }
+
+    let mut roots = Vec::with_capacity(n);
+    find_roots(&f, rng, &mut roots);
+    Ok(roots)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::thread_rng;
+
+    // Power sum S_i of the given messages.
+    fn power_sum(messages: &[Fp], i: u32) -> Fp {
+        messages.iter().fold(zero(), |acc, &m| acc + m.pow(i as u128))
+    }
+
+    #[test]
+    fn recovers_distinct_messages() {
+        let messages = vec![Fp::from_u127(3), Fp::from_u127(7), Fp::from_u127(42)];
+        let power_sums: Vec<Fp> = (1..=messages.len() as u32)
+            .map(|i| power_sum(&messages, i))
+            .collect();
+
+        let mut rng = thread_rng();
+        let mut recovered = recover_messages(&power_sums, &mut rng).unwrap();
+        recovered.sort();
+
+        let mut expected = messages.clone();
+        expected.sort();
+        assert_eq!(recovered, expected);
+    }
+
+    #[test]
+    fn detects_collision() {
+        let messages = vec![Fp::from_u127(5), Fp::from_u127(5), Fp::from_u127(9)];
+        let power_sums: Vec<Fp> = (1..=messages.len() as u32)
+            .map(|i| power_sum(&messages, i))
+            .collect();
+
+        let mut rng = thread_rng();
+        assert_eq!(recover_messages(&power_sums, &mut rng), Err(RecoverError::Collision));
+    }
+}
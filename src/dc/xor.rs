@@ -1,14 +1,40 @@
 use std::ops::{BitXor, BitXorAssign, Add, AddAssign, Sub, SubAssign, Neg};
 use std::iter::FromIterator;
 use rand::{Rand, Rng};
+use rand_chacha::ChaCha20Rng;
+use rand_chacha::rand_core::{RngCore, SeedableRng};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 use super::Randomize;
 
+// Below this many elements, dispatching to the thread pool costs more than
+// it saves; XOR the slot serially instead.
+#[cfg(feature = "rayon")]
+const PARALLEL_THRESHOLD: usize = 4096;
+
 pub struct DcXorElem<T>(Vec<T>);
 
 pub type DcXorMsg = DcXorElem<u8>;
 pub type DcXorMsgVec = DcXorElem<DcXorMsg>;
 
+impl DcXorMsg {
+    // Derives a deterministic pad from a 32-byte pairwise shared secret
+    // and a per-run/per-slot counter, so two participants who agree on
+    // `key` and `nonce` (e.g. from a Diffie-Hellman secret and the round's
+    // slot index) independently produce the exact same pad and XOR-cancel
+    // it, rather than relying on thread-local randomness.
+    pub fn masked_from_seed(key: [u8; 32], nonce: u64, len: usize) -> Self {
+        let mut rng = ChaCha20Rng::from_seed(key);
+        rng.set_stream(nonce);
+
+        let mut bytes = vec![0u8; len];
+        rng.fill_bytes(&mut bytes);
+        DcXorElem(bytes)
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
 impl<T> BitXor for DcXorElem<T>
 where
     T: BitXor,
@@ -29,6 +55,39 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<T> BitXor for DcXorElem<T>
+where
+    T: BitXor + Send,
+    <T as BitXor>::Output: Send,
+    Vec<T>: FromIterator<<T as BitXor>::Output> + FromParallelIterator<<T as BitXor>::Output>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn bitxor(self, rhs: Self) -> Self {
+        debug_assert_eq!(self.0.len(), rhs.0.len());
+        if self.0.len() >= PARALLEL_THRESHOLD {
+            DcXorElem(
+                self.0
+                    .into_par_iter()
+                    .zip(rhs.0.into_par_iter())
+                    .map(|(a, b)| T::bitxor(a, b))
+                    .collect(),
+            )
+        } else {
+            DcXorElem(
+                self.0
+                    .into_iter()
+                    .zip(rhs.0.into_iter())
+                    .map(|(a, b)| T::bitxor(a, b))
+                    .collect(),
+            )
+        }
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
 impl<T> BitXorAssign for DcXorElem<T>
 where
     T: BitXorAssign,
@@ -46,6 +105,28 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<T> BitXorAssign for DcXorElem<T>
+where
+    T: BitXorAssign + Send + Sync,
+{
+    #[inline]
+    fn bitxor_assign(&mut self, rhs: Self) {
+        debug_assert_eq!(self.0.len(), rhs.0.len());
+        if self.0.len() >= PARALLEL_THRESHOLD {
+            self.0
+                .par_iter_mut()
+                .zip(rhs.0.into_par_iter())
+                .for_each(|(a, b)| T::bitxor_assign(a, b));
+        } else {
+            for (a, b) in self.0.iter_mut().zip(rhs.0.into_iter()) {
+                T::bitxor_assign(a, b);
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
 impl<T> Add for DcXorElem<T>
 where
     T: BitXor,
@@ -59,6 +140,22 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<T> Add for DcXorElem<T>
+where
+    T: BitXor + Send,
+    <T as BitXor>::Output: Send,
+    Vec<T>: FromIterator<<T as BitXor>::Output> + FromParallelIterator<<T as BitXor>::Output>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn add(self, rhs: Self) -> Self {
+        Self::bitxor(self, rhs)
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
 impl<T> AddAssign for DcXorElem<T>
 where
     T: BitXor + BitXorAssign,
@@ -69,6 +166,18 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<T> AddAssign for DcXorElem<T>
+where
+    T: BitXor + BitXorAssign + Send + Sync,
+{
+    #[inline]
+    fn add_assign(&mut self, rhs: Self) {
+        Self::bitxor_assign(self, rhs)
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
 impl<T> Sub for DcXorElem<T>
 where
     T: BitXor,
@@ -82,6 +191,22 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<T> Sub for DcXorElem<T>
+where
+    T: BitXor + Send,
+    <T as BitXor>::Output: Send,
+    Vec<T>: FromIterator<<T as BitXor>::Output> + FromParallelIterator<<T as BitXor>::Output>,
+{
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self {
+        Self::bitxor(self, rhs)
+    }
+}
+
+#[cfg(not(feature = "rayon"))]
 impl<T> SubAssign for DcXorElem<T>
 where
     T: BitXor + BitXorAssign,
@@ -92,6 +217,17 @@ where
     }
 }
 
+#[cfg(feature = "rayon")]
+impl<T> SubAssign for DcXorElem<T>
+where
+    T: BitXor + BitXorAssign + Send + Sync,
+{
+    #[inline]
+    fn sub_assign(&mut self, rhs: Self) {
+        Self::bitxor_assign(self, rhs)
+    }
+}
+
 impl<T> Neg for DcXorElem<T> {
     type Output = Self;
 
@@ -140,3 +276,50 @@ impl<T> Randomize for DcXorElem<T> where T: Randomize {
         self.0.randomize(rng);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn masked_from_seed_is_deterministic() {
+        let key = [7u8; 32];
+        let a = DcXorMsg::masked_from_seed(key, 1, 64);
+        let b = DcXorMsg::masked_from_seed(key, 1, 64);
+        assert_eq!(a.0, b.0);
+    }
+
+    #[test]
+    fn masked_from_seed_differs_by_nonce() {
+        let key = [7u8; 32];
+        let a = DcXorMsg::masked_from_seed(key, 1, 64);
+        let b = DcXorMsg::masked_from_seed(key, 2, 64);
+        assert_ne!(a.0, b.0);
+    }
+
+    #[test]
+    fn masked_from_seed_differs_by_key() {
+        let a = DcXorMsg::masked_from_seed([7u8; 32], 1, 64);
+        let b = DcXorMsg::masked_from_seed([9u8; 32], 1, 64);
+        assert_ne!(a.0, b.0);
+    }
+
+    // Exercises both the serial and rayon-backed branches of `bitxor`,
+    // below and above `PARALLEL_THRESHOLD`, against a naive byte-wise XOR.
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn bitxor_matches_naive_below_and_above_threshold() {
+        for &len in &[8usize, PARALLEL_THRESHOLD + 8] {
+            let a: Vec<u8> = (0..len).map(|i| i as u8).collect();
+            let b: Vec<u8> = (0..len).map(|i| (i as u8).wrapping_mul(3).wrapping_add(1)).collect();
+            let expected: Vec<u8> = a.iter().zip(b.iter()).map(|(x, y)| x ^ y).collect();
+
+            let result = DcXorElem(a.clone()) ^ DcXorElem(b.clone());
+            assert_eq!(result.0, expected);
+
+            let mut assigned = DcXorElem(a);
+            assigned ^= DcXorElem(b);
+            assert_eq!(assigned.0, expected);
+        }
+    }
+}